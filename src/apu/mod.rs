@@ -1,4 +1,5 @@
 use crate::cartridge::Cartridge;
+use crate::compat::{CompatTracker, Unsupported};
 use crate::memory::ROM;
 use tracing::{event, Level};
 
@@ -14,12 +15,14 @@ impl ApuStatus {
     const R_PULSE2_ACTIVE: u8 = 0x80;
 }
 
+#[derive(Clone)]
 pub struct APU {
     pulse_1: Pulse,
     pulse_2: Pulse,
     triangle: Triangle,
     noise: Noise,
     dmc: Dmc,
+    compat: CompatTracker,
 }
 
 impl APU {
@@ -30,9 +33,14 @@ impl APU {
             triangle: Triangle::default(),
             noise: Noise::default(),
             dmc: Dmc::new(game.dpcm()),
+            compat: CompatTracker::default(),
         }
     }
 
+    pub fn unsupported_features(&self) -> Unsupported {
+        self.compat.seen()
+    }
+
     pub fn register_read(&mut self, addr: u16) -> u8 {
         let ret = match addr {
             0x0..0x4 => self.pulse_1.register_read(addr),
@@ -71,7 +79,14 @@ impl APU {
             0x4..0x8 => self.pulse_2.register_write(addr - 0x4, val),
             0x8..0xC => self.triangle.register_write(addr - 0x8, val),
             0xC..0x10 => self.noise.register_write(addr - 0xC, val),
-            0x10..0x14 => self.dmc.register_write(addr - 0x10, val),
+            0x10..0x14 => {
+                self.dmc.register_write(addr - 0x10, val);
+
+                // FIXME: The APU is never clocked, so the DMC can't raise an IRQ
+                if self.dmc.irq_en {
+                    self.compat.report(Unsupported::DMC_IRQ);
+                }
+            }
             0x14 => event!(
                 Level::DEBUG,
                 "apu::register_write ignored ({:#X}, ={:#X})",
@@ -107,6 +122,7 @@ impl APU {
     }
 }
 
+#[derive(Clone)]
 struct Dmc {
     irq_en: bool,
     irq_raised: bool,
@@ -277,7 +293,7 @@ impl Dmc {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Noise {
     v_loop: bool,
     v_const: bool,
@@ -318,7 +334,7 @@ impl Noise {
 }
 
 // https://www.nesdev.org/wiki/APU_Sweep
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct SweepUnit {
     divider: Divider,
     pub shift: u8,
@@ -343,7 +359,7 @@ impl SweepUnit {
 }
 
 // https://www.nesdev.org/wiki/APU_Envelope
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct EnvelopeGenerator {
     divider: Divider,
     decay_counter: u8,
@@ -386,7 +402,7 @@ impl EnvelopeGenerator {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Divider {
     reload: u16,
     counter: u16,
@@ -413,7 +429,7 @@ impl Divider {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct LengthCounter {
     counter: u8,
     pub enabled: bool,
@@ -438,7 +454,7 @@ impl LengthCounter {
 }
 
 // FIXME: This is clocked every 1/2 frame, so two clocks may need to happen every frame
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Pulse {
     duty: u8,
     envelope_gen: EnvelopeGenerator,
@@ -498,7 +514,7 @@ impl Pulse {
     }
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Triangle {
     halt: bool,
     linear_load: u8,
@@ -1,7 +1,9 @@
 use crate::apu::*;
 use crate::cartridge::*;
+use crate::compat::{CompatTracker, Unsupported};
 use crate::controller::*;
 use crate::graphics::Renderer;
+use crate::input::{Button, Player};
 use crate::memory::*;
 use crate::ppu::*;
 use crate::timer;
@@ -21,42 +23,95 @@ pub trait Bus {
     fn cycles(&self) -> usize;
     fn clock(&mut self, cycles: usize);
     fn pop_nmi(&mut self) -> Option<u8>;
+    fn irq_pending(&self) -> bool {
+        false
+    }
     fn ppu_state(&self) -> (i16, i16) {
         (0, 0)
     }
 }
 
+#[derive(Clone)]
 pub struct NesBus {
     game: Cartridge,
-    _controller1: Controller,
-    _controller2: Controller,
+    controller1: Controller,
+    controller2: Controller,
     ppu: PPU,
     apu: APU,
     cpu_ram: RAM,
     nmi: Option<u8>,
+    compat: CompatTracker,
 
     total_cycles: usize,
     cycles_last_sync: usize,
     last_sync: timer::FastInstant,
+    throttle: bool,
 }
 
 impl NesBus {
     pub fn new(game: Cartridge, renderer: Box<dyn Renderer>) -> Self {
         NesBus {
-            _controller1: Controller::new(),
-            _controller2: Controller::new(),
+            controller1: Controller::new(),
+            controller2: Controller::new(),
             ppu: PPU::new(&game, renderer),
             apu: APU::new(&game),
             game,
             cpu_ram: RAM::with_size(0x800),
             nmi: None,
+            compat: CompatTracker::default(),
 
             total_cycles: 0,
             cycles_last_sync: 0,
             last_sync: timer::FastInstant::now(),
+            throttle: true,
         }
     }
 
+    /// Enable or disable limiting the emulation speed to the NTSC clock rate
+    pub fn set_throttle(&mut self, throttle: bool) {
+        self.throttle = throttle;
+        self.cycles_last_sync = 0;
+        self.last_sync = timer::FastInstant::now();
+    }
+
+    /// Unsupported features the game has used so far
+    pub fn unsupported_features(&self) -> Unsupported {
+        self.compat.seen() | self.ppu.unsupported_features() | self.apu.unsupported_features()
+    }
+
+    pub fn throttle(&self) -> bool {
+        self.throttle
+    }
+
+    pub fn set_renderer(&mut self, renderer: Box<dyn Renderer>) -> Box<dyn Renderer> {
+        self.ppu.set_renderer(renderer)
+    }
+
+    pub fn cpu_ram(&self) -> &[u8] {
+        &self.cpu_ram
+    }
+
+    pub fn frame(&self) -> usize {
+        self.ppu.frame()
+    }
+
+    pub fn frame_buffer(&self) -> &[u32] {
+        self.ppu.frame_buffer()
+    }
+
+    pub fn set_button(&mut self, player: Player, button: Button, pressed: bool) {
+        let controller = match player {
+            Player::One => &mut self.controller1,
+            Player::Two => &mut self.controller2,
+        };
+        controller.set_button(button, pressed);
+    }
+
+    pub fn release_all_buttons(&mut self) {
+        self.controller1.release_all();
+        self.controller2.release_all();
+    }
+
     fn dump_access(&self, ty: &str, addr: u16, value: u8) {
         event!(
             Level::DEBUG,
@@ -70,7 +125,7 @@ impl NesBus {
 
     fn throttle_to_ntsc(&mut self) {
         const FREERUN_CYCLES: usize = 20_000;
-        if self.cycles_last_sync < FREERUN_CYCLES {
+        if !self.throttle || self.cycles_last_sync < FREERUN_CYCLES {
             return;
         }
 
@@ -96,20 +151,22 @@ impl Bus for NesBus {
             0x0..=0x1FFF => self.cpu_ram[addr as usize & 0x7FF],
             0x2000..=0x3FFF => self.ppu.register_read(addr - 0x2000),
             0x4000..=0x4015 => self.apu.register_read(addr - 0x4000),
-            0x4016 => {
-                event!(Level::DEBUG, "read from controller 1");
-                0
-            }
-            0x4017 => {
-                event!(Level::DEBUG, "read from controller 2");
-                0
-            }
+            0x4016 => self.controller1.read(),
+            0x4017 => self.controller2.read(),
             0x4018..=0x401F => {
                 event!(Level::DEBUG, "read from APU.test");
+                self.compat.report(Unsupported::APU_TEST_REGISTERS);
+                0
+            }
+            // FIXME: None of the supported mappers have anything here. This should move into the
+            // mapper once one does
+            0x4020..=0x5FFF => {
+                event!(Level::DEBUG, "read from expansion area");
+                self.compat.report(Unsupported::EXPANSION_REGISTERS);
                 0
             }
             // NOTE: Cartridges use absolute addresses
-            0x4020..=0xFFFF => self.game.prg_read(addr),
+            0x6000..=0xFFFF => self.game.prg_read(addr),
         };
         self.dump_access("read", addr, value);
 
@@ -124,8 +181,12 @@ impl Bus for NesBus {
             0x0..=0x1FFF => self.cpu_ram[addr as usize & 0x7FF] = val,
             0x2000..=0x3FFF => self.ppu.register_write(addr - 0x2000, val),
             0x4000..0x4014 | 0x4015 => self.apu.register_write(addr - 0x4000, val),
-            // NOTE: Controllers can be written to to enable strobe mode
-            0x4016 => event!(Level::DEBUG, "write to controller 1"),
+            // NOTE: Controllers can be written to to enable strobe mode. Both controllers share the
+            // strobe line on $4016
+            0x4016 => {
+                self.controller1.write(val);
+                self.controller2.write(val);
+            }
             0x4017 => event!(Level::DEBUG, "write to controller 2"),
             0x4014 => {
                 event!(
@@ -152,9 +213,16 @@ impl Bus for NesBus {
                     .collect::<Vec<_>>();
                 self.ppu.oam_dma(dma_buffer.as_slice());
             }
+            0x4018..=0x401F => {
+                event!(Level::DEBUG, "write to APU.test");
+                self.compat.report(Unsupported::APU_TEST_REGISTERS);
+            }
+            0x4020..=0x5FFF => {
+                event!(Level::DEBUG, "write to expansion area");
+                self.compat.report(Unsupported::EXPANSION_REGISTERS);
+            }
             // NOTE: Cartridges use absolute addresses
-            0x4020..=0xFFFF => self.game.prg_write(addr, val),
-            _ => unreachable!(),
+            0x6000..=0xFFFF => self.game.prg_write(addr, val),
         }
     }
 
@@ -185,4 +253,8 @@ impl Bus for NesBus {
         self.nmi = None;
         nmi
     }
+
+    fn irq_pending(&self) -> bool {
+        self.apu.irq_raised()
+    }
 }
@@ -1,6 +1,7 @@
 use super::*;
 use crate::memory::*;
 
+#[derive(Clone)]
 pub struct Mapper0 {
     // for CPU
     prg_rom: ROM,
@@ -60,6 +61,10 @@ impl Mapper for Mapper0 {
     fn chr(&self) -> ROM {
         ROM::with_data(&self.chr_ram)
     }
+
+    fn box_clone(&self) -> Box<dyn Mapper> {
+        Box::new(self.clone())
+    }
 }
 
 impl Mapper0 {
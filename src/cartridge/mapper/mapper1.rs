@@ -1,6 +1,7 @@
 use super::*;
 use crate::memory::*;
 
+#[derive(Clone)]
 pub struct Mapper1 {
     prg_rom: ROM, // for CPU
     prg_ram: RAM, // for CPU
@@ -48,6 +49,10 @@ impl Mapper for Mapper1 {
     fn chr(&self) -> ROM {
         ROM::with_data(&self.chr_ram)
     }
+
+    fn box_clone(&self) -> Box<dyn Mapper> {
+        Box::new(self.clone())
+    }
 }
 
 impl Mapper1 {
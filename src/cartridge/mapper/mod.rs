@@ -48,6 +48,13 @@ pub trait Mapper {
     fn prg_write(&mut self, addr: u16, val: u8);
     fn chr(&self) -> ROM;
     fn dpcm(&self) -> ROM;
+    fn box_clone(&self) -> Box<dyn Mapper>;
+}
+
+impl Clone for Box<dyn Mapper> {
+    fn clone(&self) -> Self {
+        self.box_clone()
+    }
 }
 
 impl fmt::Debug for Box<dyn Mapper> {
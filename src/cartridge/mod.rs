@@ -7,7 +7,7 @@ use mapper::*;
 use std::io::Read;
 use tracing::{event, Level};
 
-#[derive(Debug, Default)]
+#[derive(Clone, Debug, Default)]
 pub struct Cartridge {
     name: String,
     header: Header,
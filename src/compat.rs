@@ -0,0 +1,114 @@
+use tracing::{event, Level};
+
+bitflags! {
+    /// Hardware features a game may rely on which are not emulated yet
+    pub struct Unsupported: u8 {
+        const CHR_WRITE           = 0x01;
+        const COLOR_EMPHASIS      = 0x02;
+        const GRAYSCALE           = 0x04;
+        const DMC_IRQ             = 0x08;
+        const APU_TEST_REGISTERS  = 0x10;
+        const EXPANSION_REGISTERS = 0x20;
+    }
+}
+
+const DESCRIPTIONS: [(Unsupported, &str); 6] = [
+    (
+        Unsupported::CHR_WRITE,
+        "writes to CHR memory (CHR RAM), graphics may be missing or corrupt",
+    ),
+    (
+        Unsupported::COLOR_EMPHASIS,
+        "PPUMASK color emphasis, colors may be wrong",
+    ),
+    (
+        Unsupported::GRAYSCALE,
+        "PPUMASK grayscale mode, colors may be wrong",
+    ),
+    (
+        Unsupported::DMC_IRQ,
+        "DMC IRQs, timing that depends on them will be off",
+    ),
+    (
+        Unsupported::APU_TEST_REGISTERS,
+        "APU test registers ($4018-$401F)",
+    ),
+    (
+        Unsupported::EXPANSION_REGISTERS,
+        "cartridge expansion registers ($4020-$5FFF), e.g. expansion audio",
+    ),
+];
+
+impl Unsupported {
+    pub fn descriptions(&self) -> impl Iterator<Item = &'static str> + '_ {
+        DESCRIPTIONS
+            .iter()
+            .filter(move |(feature, _)| self.contains(*feature))
+            .map(|(_, description)| *description)
+    }
+}
+
+/// Records unsupported features as the game uses them. A warning is logged the first time each
+/// feature is seen
+#[derive(Clone)]
+pub struct CompatTracker {
+    seen: Unsupported,
+}
+
+impl Default for CompatTracker {
+    fn default() -> Self {
+        CompatTracker {
+            seen: Unsupported::empty(),
+        }
+    }
+}
+
+impl CompatTracker {
+    pub fn report(&mut self, features: Unsupported) {
+        let new = features - self.seen;
+        if new.is_empty() {
+            return;
+        }
+
+        for description in new.descriptions() {
+            event!(
+                target: "venus::compat",
+                Level::WARN,
+                "Game uses unsupported feature: {}",
+                description
+            );
+        }
+        self.seen |= new;
+    }
+
+    pub fn seen(&self) -> Unsupported {
+        self.seen
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn report() {
+        let mut tracker = CompatTracker::default();
+        assert!(tracker.seen().is_empty());
+
+        tracker.report(Unsupported::CHR_WRITE);
+        tracker.report(Unsupported::CHR_WRITE | Unsupported::GRAYSCALE);
+        assert_eq!(
+            tracker.seen(),
+            Unsupported::CHR_WRITE | Unsupported::GRAYSCALE
+        );
+    }
+
+    #[test]
+    fn descriptions() {
+        assert_eq!(Unsupported::empty().descriptions().count(), 0);
+        assert_eq!(
+            Unsupported::all().descriptions().count(),
+            DESCRIPTIONS.len()
+        );
+    }
+}
@@ -1,8 +1,97 @@
+use crate::input::Button;
+
+/// Standard NES controller. The button states are latched into a shift register while the strobe
+/// bit is set, and then read out one bit at a time in the order A, B, Select, Start, Up, Down,
+/// Left, Right. After all 8 bits have been read, official controllers return 1
+///
+/// https://www.nesdev.org/wiki/Standard_controller
 #[derive(Default, Clone)]
-pub struct Controller {}
+pub struct Controller {
+    buttons: u8,
+    shift: u8,
+    strobe: bool,
+}
 
 impl Controller {
     pub fn new() -> Self {
-        Controller {}
+        Controller::default()
+    }
+
+    pub fn set_button(&mut self, button: Button, pressed: bool) {
+        let mask = 1 << button as u8;
+        if pressed {
+            self.buttons |= mask;
+        } else {
+            self.buttons &= !mask;
+        }
+    }
+
+    pub fn release_all(&mut self) {
+        self.buttons = 0;
+    }
+
+    /// The buttons are reloaded continuously while strobe is high, so the state at the 1 -> 0 edge
+    /// is what gets shifted out
+    pub fn write(&mut self, val: u8) {
+        let was_strobe = self.strobe;
+        self.strobe = val & 0x1 != 0;
+        if self.strobe || was_strobe {
+            self.shift = self.buttons;
+        }
+    }
+
+    pub fn read(&mut self) -> u8 {
+        if self.strobe {
+            return self.buttons & 0x1;
+        }
+
+        let bit = self.shift & 0x1;
+        self.shift = (self.shift >> 1) | 0x80;
+        bit
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shift_out_buttons() {
+        let mut controller = Controller::new();
+        controller.set_button(Button::A, true);
+        controller.set_button(Button::Start, true);
+        controller.set_button(Button::Left, true);
+
+        controller.write(1);
+        controller.write(0);
+
+        let bits = (0..8).map(|_| controller.read()).collect::<Vec<_>>();
+        assert_eq!(bits, [1, 0, 0, 1, 0, 0, 1, 0]);
+
+        // Reads past the 8th button are always 1
+        assert_eq!(controller.read(), 1);
+
+        // A press between setting and clearing strobe is latched on the falling edge
+        controller.write(1);
+        controller.set_button(Button::B, true);
+        controller.write(0);
+
+        let bits = (0..8).map(|_| controller.read()).collect::<Vec<_>>();
+        assert_eq!(bits, [1, 1, 0, 1, 0, 0, 1, 0]);
+    }
+
+    #[test]
+    fn strobe_reads_a() {
+        let mut controller = Controller::new();
+        controller.write(1);
+        assert_eq!(controller.read(), 0);
+
+        controller.set_button(Button::A, true);
+        assert_eq!(controller.read(), 1);
+        assert_eq!(controller.read(), 1);
+
+        controller.set_button(Button::A, false);
+        controller.set_button(Button::B, true);
+        assert_eq!(controller.read(), 0);
     }
 }
@@ -1,8 +1,10 @@
 use super::*;
 use timer;
 
+#[derive(Clone)]
 pub struct Interpreter<T: Bus> {
     pub bus: T,
+    pub interrupt_log: InterruptLog,
     instruction: Instruction,
     operands: Vec<u8>,
     extra_cycles: usize,
@@ -12,6 +14,7 @@ impl<T: Bus> Interpreter<T> {
     pub fn new(bus: T) -> Self {
         Interpreter {
             bus,
+            interrupt_log: InterruptLog::default(),
             instruction: Instruction::default(),
             operands: Vec::with_capacity(2),
             extra_cycles: 0,
@@ -51,6 +54,7 @@ impl<T: Bus> Interpreter<T> {
         use super::instructions::InstrName::*;
 
         self.extra_cycles = 0;
+        let int_disable_before = state.status.contains(Status::INT_DISABLE);
 
         let next_pc = match self.instruction.name() {
             // BRANCHES
@@ -137,6 +141,15 @@ impl<T: Bus> Interpreter<T> {
             ILLEGAL_NOP | NOP => self.nop(state),
         };
 
+        // CLI, SEI and PLP poll for interrupts before changing the I flag, so the new value only
+        // takes effect after the next instruction. RTI changes the flag immediately
+        //
+        // https://www.nesdev.org/wiki/CPU_interrupts#Delayed_IRQ_response_after_CLI,_SEI,_and_PLP
+        state.delayed_int_disable = match self.instruction.name() {
+            CLI | SEI | PLP => Some(int_disable_before),
+            _ => None,
+        };
+
         trace_instruction(state, &self.instruction, &self.operands);
 
         state.instructions_executed += 1;
@@ -392,13 +405,12 @@ impl<T: Bus> Interpreter<T> {
 
     fn brk(&mut self, state: &mut CpuState) -> Option<u16> {
         self.push16(state, state.pc.wrapping_add(2));
-        self.push8(
-            state,
-            state.status.bits() | Status::BRK.bits() | Status::PUSH_IRQ.bits(),
-        );
+        self.push_status(state, true);
         state.status.set(Status::INT_DISABLE, true);
 
-        Some(self.bus.read16(IRQ_VECTOR_START))
+        let handler = self.bus.read16(IRQ_VECTOR_START);
+        self.log_interrupt(InterruptKind::Brk, IRQ_VECTOR_START, handler, state.pc);
+        Some(handler)
     }
 
     fn clc(&mut self, state: &mut CpuState) -> Option<u16> {
@@ -584,15 +596,13 @@ impl<T: Bus> Interpreter<T> {
     }
 
     fn php(&mut self, state: &mut CpuState) -> Option<u16> {
-        self.push8(state, (state.status | Status::BRK).bits());
+        self.push_status(state, true);
 
         None
     }
 
     fn plp(&mut self, state: &mut CpuState) -> Option<u16> {
-        state.status =
-            Status::from_bits(self.pop8(state) & !Status::BRK.bits() | Status::PUSH_IRQ.bits())
-                .expect("All bits are covered in Status");
+        self.pop_status(state);
 
         None
     }
@@ -624,10 +634,7 @@ impl<T: Bus> Interpreter<T> {
     }
 
     fn rti(&mut self, state: &mut CpuState) -> Option<u16> {
-        state.status = Status::from_bits(self.pop8(state) & !Status::BRK.bits())
-            .expect("All bits are covered in Status");
-        state.status.set(Status::PUSH_IRQ, true);
-
+        self.pop_status(state);
         Some(self.pop16(state))
     }
 
@@ -939,6 +946,7 @@ impl<T: Bus> Interpreter<T> {
 
         state.pc = pc;
         state.status = Status::default();
+        state.delayed_int_disable = None;
         state.sp = 0xFD;
     }
 
@@ -949,17 +957,72 @@ impl<T: Bus> Interpreter<T> {
         }
 
         self.push16(state, state.pc);
-        self.push8(state, state.status.bits());
+        self.push_status(state, false);
         state.status.set(Status::INT_DISABLE, true);
+        state.delayed_int_disable = None;
 
         // Load address of interrupt handler, set PC to execute there
-        state.pc = self.bus.read16(NMI_VECTOR_START);
-        event!(Level::TRACE, "IRQ: {:#04X}", state.pc);
+        let handler = self.bus.read16(NMI_VECTOR_START);
+        self.log_interrupt(InterruptKind::Nmi, NMI_VECTOR_START, handler, state.pc);
+        state.pc = handler;
 
         const NMI_CYCLES: usize = 2;
         Some(NMI_CYCLES)
     }
 
+    pub fn handle_irq(&mut self, state: &mut CpuState) -> Option<usize> {
+        let int_disable = state
+            .delayed_int_disable
+            .unwrap_or(state.status.contains(Status::INT_DISABLE));
+        if int_disable || !self.bus.irq_pending() {
+            return None;
+        }
+
+        self.push16(state, state.pc);
+        self.push_status(state, false);
+        state.status.set(Status::INT_DISABLE, true);
+        state.delayed_int_disable = None;
+
+        let handler = self.bus.read16(IRQ_VECTOR_START);
+        self.log_interrupt(InterruptKind::Irq, IRQ_VECTOR_START, handler, state.pc);
+        state.pc = handler;
+
+        const IRQ_CYCLES: usize = 7;
+        Some(IRQ_CYCLES)
+    }
+
+    /// The B flag does not exist in the status register, it is only set in the copy pushed to the
+    /// stack by PHP and BRK. Bit 5 is always pushed as 1
+    ///
+    /// https://www.nesdev.org/wiki/Status_flags#The_B_flag
+    fn push_status(&mut self, state: &mut CpuState, brk: bool) {
+        let mut status = state.status | Status::PUSH_IRQ;
+        status.set(Status::BRK, brk);
+        self.push8(state, status.bits());
+    }
+
+    /// PLP and RTI ignore bits 4 and 5 of the pulled value
+    fn pop_status(&mut self, state: &mut CpuState) {
+        let status = Status::from_bits_truncate(self.pop8(state));
+        state.status = (status - Status::BRK) | Status::PUSH_IRQ;
+    }
+
+    fn log_interrupt(&mut self, kind: InterruptKind, vector: u16, handler: u16, pc: u16) {
+        let (scanline, _) = self.bus.ppu_state();
+        let entry = InterruptEntry {
+            kind,
+            vector,
+            handler,
+            pc,
+            scanline,
+            total_cycles: self.bus.cycles(),
+        };
+
+        // Homebrew developers can enable this target on its own to follow their handlers
+        event!(target: "venus::interrupts", Level::INFO, "{}", entry);
+        self.interrupt_log.push(entry);
+    }
+
     // FIXME: At some point, these should not use the Bus. But I'm not sure how to get the
     // dispatching right at the moment so we don't need to sprinkle the address map everywhere
     fn push16(&mut self, state: &mut CpuState, v: u16) {
@@ -0,0 +1,143 @@
+use std::collections::VecDeque;
+use std::fmt;
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum InterruptKind {
+    Nmi,
+    Irq,
+    Brk,
+}
+
+/// A single entry into an interrupt handler
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct InterruptEntry {
+    pub kind: InterruptKind,
+    pub vector: u16,
+    pub handler: u16,
+    pub pc: u16,
+    pub scanline: i16,
+    pub total_cycles: usize,
+}
+
+impl fmt::Display for InterruptEntry {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{:<3} PC:{:04X} -> {:04X} (vector {:04X})  SL:{:<3}  CYC:{}",
+            format!("{:?}", self.kind).to_uppercase(),
+            self.pc,
+            self.handler,
+            self.vector,
+            self.scanline,
+            self.total_cycles,
+        )
+    }
+}
+
+/// Ring buffer of the most recent interrupt entries. This is meant to help debugging interrupt
+/// handlers, so it is kept small and only touched when an interrupt is taken. A capacity of 0
+/// disables logging entirely
+#[derive(Clone, Debug)]
+pub struct InterruptLog {
+    entries: VecDeque<InterruptEntry>,
+    capacity: usize,
+}
+
+impl Default for InterruptLog {
+    fn default() -> Self {
+        const DEFAULT_CAPACITY: usize = 64;
+        InterruptLog::with_capacity(DEFAULT_CAPACITY)
+    }
+}
+
+impl InterruptLog {
+    pub fn with_capacity(capacity: usize) -> Self {
+        InterruptLog {
+            entries: VecDeque::with_capacity(capacity),
+            capacity,
+        }
+    }
+
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Resize the log, dropping the oldest entries if it shrinks
+    pub fn set_capacity(&mut self, capacity: usize) {
+        self.capacity = capacity;
+        while self.entries.len() > capacity {
+            self.entries.pop_front();
+        }
+    }
+
+    pub fn push(&mut self, entry: InterruptEntry) {
+        if self.capacity == 0 {
+            return;
+        }
+
+        if self.entries.len() == self.capacity {
+            self.entries.pop_front();
+        }
+        self.entries.push_back(entry);
+    }
+
+    /// Entries from oldest to newest
+    pub fn entries(&self) -> impl Iterator<Item = &InterruptEntry> {
+        self.entries.iter()
+    }
+
+    pub fn last(&self) -> Option<&InterruptEntry> {
+        self.entries.back()
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(pc: u16) -> InterruptEntry {
+        InterruptEntry {
+            kind: InterruptKind::Nmi,
+            vector: 0xFFFA,
+            handler: 0x8000,
+            pc,
+            scanline: 241,
+            total_cycles: 0,
+        }
+    }
+
+    #[test]
+    fn ring() {
+        let mut log = InterruptLog::with_capacity(2);
+        log.push(entry(1));
+        log.push(entry(2));
+        log.push(entry(3));
+
+        let pcs = log.entries().map(|e| e.pc).collect::<Vec<_>>();
+        assert_eq!(pcs, [2, 3]);
+        assert_eq!(log.last().unwrap().pc, 3);
+
+        log.set_capacity(1);
+        assert_eq!(log.len(), 1);
+        assert_eq!(log.last().unwrap().pc, 3);
+    }
+
+    #[test]
+    fn disabled() {
+        let mut log = InterruptLog::with_capacity(0);
+        log.push(entry(1));
+        assert!(log.is_empty());
+    }
+}
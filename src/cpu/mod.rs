@@ -1,5 +1,6 @@
 pub mod instructions;
 mod interpreter;
+pub mod interrupt_log;
 mod status;
 
 use {
@@ -7,6 +8,7 @@ use {
     crate::timer,
     crate::ExitStatus,
     instructions::Instruction,
+    interrupt_log::{InterruptEntry, InterruptKind, InterruptLog},
     status::Status,
     std::stringify,
     tracing::{event, span, Level},
@@ -123,6 +125,7 @@ pub trait CpuInterface {
     fn read_state(&self) -> NESSnapshot;
     fn read_address(&mut self, addr: u16) -> u8;
     fn request_stop(&mut self, code: i32);
+    fn interrupt_log(&self) -> &InterruptLog;
 }
 
 impl<BusType: Bus> CpuInterface for CPU<BusType> {
@@ -152,9 +155,14 @@ impl<BusType: Bus> CpuInterface for CPU<BusType> {
     fn request_stop(&mut self, retcode: i32) {
         self.exit_status = ExitStatus::StopRequested(retcode);
     }
+
+    fn interrupt_log(&self) -> &InterruptLog {
+        &self.interpreter.interrupt_log
+    }
 }
 
 // State which is shared between the interpreter and the binary translator
+#[derive(Clone)]
 struct CpuState {
     acc: u8,
     x: u8,
@@ -163,6 +171,9 @@ struct CpuState {
     sp: u8,
     status: Status,
 
+    // Value of the I flag seen when polling for IRQs, if it differs from the status register
+    delayed_int_disable: Option<bool>,
+
     instructions_executed: usize,
 }
 
@@ -175,6 +186,7 @@ impl CpuState {
             pc: 0,
             sp: 0xFD,
             status: Status::empty(),
+            delayed_int_disable: None,
             instructions_executed: 0,
         }
     }
@@ -186,6 +198,7 @@ impl CpuState {
     }
 }
 
+#[derive(Clone)]
 pub struct CPU<BusType: Bus> {
     state: CpuState,
     interpreter: interpreter::Interpreter<BusType>,
@@ -208,6 +221,23 @@ impl<BusType: Bus> CPU<BusType> {
         self.state.pc
     }
 
+    /// Total number of CPU cycles clocked on the bus
+    pub fn cycles(&self) -> usize {
+        self.interpreter.bus.cycles()
+    }
+
+    pub fn bus(&self) -> &BusType {
+        &self.interpreter.bus
+    }
+
+    pub fn bus_mut(&mut self) -> &mut BusType {
+        &mut self.interpreter.bus
+    }
+
+    pub fn interrupt_log_mut(&mut self) -> &mut InterruptLog {
+        &mut self.interpreter.interrupt_log
+    }
+
     pub fn nestest_reset_override(&mut self, pc: u16) {
         self.interpreter.reset(&mut self.state);
         self.state.pc = pc;
@@ -233,6 +263,8 @@ impl<BusType: Bus> CPU<BusType> {
 
             if let Some(cycles) = self.interpreter.handle_nmi(&mut self.state) {
                 cycles
+            } else if let Some(cycles) = self.interpreter.handle_irq(&mut self.state) {
+                cycles
             } else {
                 self.interpreter.interpret(&mut self.state)
             }
@@ -3,12 +3,20 @@ use crate::memory::{RAM, ROM};
 use instructions::AddressingMode::*;
 use instructions::InstrName::*;
 
+mod status;
+
 const TEST_PROGRAM_START: usize = 0x7FF0;
 
+// Distinct handlers so reading the wrong interrupt vector is caught
+const TEST_IRQ_HANDLER: u16 = 0x1234;
+const TEST_NMI_HANDLER: u16 = 0x5678;
+
 struct TestBus {
     program: ROM,
     cycles: usize,
     ram: RAM,
+    nmi: Option<u8>,
+    irq: bool,
 }
 
 impl TestBus {
@@ -17,6 +25,8 @@ impl TestBus {
             program: ROM::with_data(data),
             cycles: 0,
             ram: RAM::with_size(0x800),
+            nmi: None,
+            irq: false,
         }
     }
 }
@@ -47,17 +57,25 @@ impl Bus for TestBus {
     }
 
     fn pop_nmi(&mut self) -> Option<u8> {
-        None
+        self.nmi.take()
+    }
+
+    fn irq_pending(&self) -> bool {
+        self.irq
     }
 }
 
 fn initialize_program(data: &[u8]) -> CPU<TestBus> {
     println!("DATA: {:x?}", data);
-    let mut program = vec![0; 0xFFFF];
+    let mut program = vec![0; 0x10000];
     program[TEST_PROGRAM_START as usize..(TEST_PROGRAM_START as usize + data.len())]
         .copy_from_slice(data);
     program[RESET_VECTOR_START as usize] = (TEST_PROGRAM_START & 0xFF) as u8;
     program[RESET_VECTOR_START as usize + 1] = (TEST_PROGRAM_START >> 8) as u8;
+    program[IRQ_VECTOR_START as usize] = (TEST_IRQ_HANDLER & 0xFF) as u8;
+    program[IRQ_VECTOR_START as usize + 1] = (TEST_IRQ_HANDLER >> 8) as u8;
+    program[NMI_VECTOR_START as usize] = (TEST_NMI_HANDLER & 0xFF) as u8;
+    program[NMI_VECTOR_START as usize + 1] = (TEST_NMI_HANDLER >> 8) as u8;
 
     let bus = TestBus::new(&program);
     let mut cpu = CPU::new(bus);
@@ -398,6 +416,21 @@ fn rt() {
     //verify_op!(RTS, Invalid, 0x66, 0x00, [0x00=0x01]{} => [0x00=0x00]{status: set_status!(Status::CARRY, Status::ZERO)});
 }
 
+#[test]
+fn interrupt_log() {
+    let mut cpu = initialize_program(&[0x00, 0x00]);
+    cpu.clock();
+
+    let entry = cpu.interrupt_log().last().expect("BRK was not logged");
+    assert_eq!(entry.kind, InterruptKind::Brk);
+    assert_eq!(entry.vector, IRQ_VECTOR_START);
+    assert_eq!(entry.pc, TEST_PROGRAM_START as u16);
+    assert_eq!(cpu.state.pc, entry.handler);
+
+    cpu.interrupt_log_mut().set_capacity(0);
+    assert!(cpu.interrupt_log().is_empty());
+}
+
 // TODO: Validate overflow with other implementations
 #[test]
 fn sbc() {
@@ -0,0 +1,191 @@
+// Status register semantics for PHP/PLP/BRK/RTI and interrupts. These are checked by the
+// instr_misc and cpu_interrupts test ROMs
+//
+// https://www.nesdev.org/wiki/Status_flags
+// https://www.nesdev.org/wiki/CPU_interrupts
+use super::*;
+
+const START_PC: u16 = TEST_PROGRAM_START as u16;
+
+/// Status is always the last byte pushed by PHP, BRK and interrupts
+fn pushed_status(cpu: &mut CPU<TestBus>) -> u8 {
+    let sp = cpu.state.sp;
+    cpu.interpreter
+        .bus
+        .read(STACK_BEGIN + sp.wrapping_add(1) as u16)
+}
+
+/// Return address pushed before the status by BRK and interrupts
+fn pushed_return_address(cpu: &mut CPU<TestBus>) -> u16 {
+    let sp = cpu.state.sp;
+    let lo = cpu
+        .interpreter
+        .bus
+        .read(STACK_BEGIN + sp.wrapping_add(2) as u16) as u16;
+    let hi = cpu
+        .interpreter
+        .bus
+        .read(STACK_BEGIN + sp.wrapping_add(3) as u16) as u16;
+    (hi << 8) | lo
+}
+
+/// Lay out an RTI frame on the stack so the next pull returns `status` and `pc`
+fn push_rti_frame(cpu: &mut CPU<TestBus>, status: u8, pc: u16) {
+    cpu.state.sp = 0xFA;
+    cpu.interpreter.bus.write(0x1FB, status);
+    cpu.interpreter.bus.write(0x1FC, (pc & 0xFF) as u8);
+    cpu.interpreter.bus.write(0x1FD, (pc >> 8) as u8);
+}
+
+#[test]
+fn php_pushes_b_and_unused() {
+    let mut cpu = initialize_program(&[0x08]);
+    cpu.state.status = Status::CARRY;
+    cpu.clock();
+
+    assert_eq!(pushed_status(&mut cpu), 0x31);
+    assert_eq!(cpu.state.status, Status::CARRY, "PHP should not modify P");
+}
+
+#[test]
+fn plp_ignores_b_and_unused() {
+    let mut cpu = initialize_program(&[0x28]);
+    cpu.interpreter.bus.write(0x1FE, 0xFF);
+    cpu.clock();
+    assert_eq!(cpu.state.status.bits(), 0xEF);
+    assert_eq!(cpu.state.sp, 0xFE);
+
+    let mut cpu = initialize_program(&[0x28]);
+    cpu.interpreter.bus.write(0x1FE, 0x00);
+    cpu.clock();
+    assert_eq!(cpu.state.status, Status::PUSH_IRQ);
+}
+
+#[test]
+fn brk_pushes_b_and_sets_i() {
+    let mut cpu = initialize_program(&[0x00, 0x00]);
+    cpu.state.status = Status::NEGATIVE;
+    cpu.clock();
+
+    assert_eq!(pushed_status(&mut cpu), 0xB0);
+    assert_eq!(pushed_return_address(&mut cpu), START_PC + 2);
+    assert_eq!(cpu.state.status, Status::NEGATIVE | Status::INT_DISABLE);
+    assert_eq!(cpu.state.pc, TEST_IRQ_HANDLER);
+}
+
+#[test]
+fn rti_ignores_b_and_unused() {
+    let mut cpu = initialize_program(&[0x40]);
+    push_rti_frame(&mut cpu, 0xFF, 0x1234);
+    cpu.clock();
+
+    assert_eq!(cpu.state.status.bits(), 0xEF);
+    assert_eq!(
+        cpu.state.pc, 0x1234,
+        "RTI should not add 1 to the return address"
+    );
+    assert_eq!(cpu.state.sp, 0xFD);
+}
+
+#[test]
+fn nmi_pushes_without_b() {
+    let mut cpu = initialize_program(&[0xEA]);
+    cpu.state.status = Status::CARRY;
+    cpu.interpreter.bus.nmi = Some(1);
+    cpu.clock();
+
+    assert_eq!(pushed_status(&mut cpu), 0x21);
+    assert_eq!(pushed_return_address(&mut cpu), START_PC);
+    assert_eq!(cpu.state.status, Status::CARRY | Status::INT_DISABLE);
+    assert_eq!(cpu.state.pc, TEST_NMI_HANDLER);
+}
+
+#[test]
+fn irq_pushes_without_b() {
+    let mut cpu = initialize_program(&[0xEA]);
+    cpu.state.status = Status::CARRY;
+    cpu.interpreter.bus.irq = true;
+    cpu.clock();
+
+    assert_eq!(pushed_status(&mut cpu), 0x21);
+    assert_eq!(pushed_return_address(&mut cpu), START_PC);
+    assert_eq!(cpu.state.status, Status::CARRY | Status::INT_DISABLE);
+    assert_eq!(cpu.state.pc, TEST_IRQ_HANDLER);
+    assert_eq!(cpu.interrupt_log().last().unwrap().kind, InterruptKind::Irq);
+}
+
+#[test]
+fn irq_masked() {
+    let mut cpu = initialize_program(&[0xEA]);
+    cpu.state.status = Status::INT_DISABLE;
+    cpu.interpreter.bus.irq = true;
+    cpu.clock();
+
+    assert_eq!(cpu.state.pc, START_PC + 1);
+}
+
+#[test]
+fn cli_delays_irq() {
+    // CLI; NOP; NOP
+    let mut cpu = initialize_program(&[0x58, 0xEA, 0xEA]);
+    cpu.state.status = Status::INT_DISABLE;
+    cpu.interpreter.bus.irq = true;
+
+    cpu.clock();
+    assert!(!cpu.state.status.contains(Status::INT_DISABLE));
+
+    // The instruction after CLI still runs before the IRQ is taken
+    cpu.clock();
+    assert_eq!(cpu.state.pc, START_PC + 2);
+
+    cpu.clock();
+    assert_eq!(cpu.state.pc, TEST_IRQ_HANDLER);
+    assert_eq!(pushed_return_address(&mut cpu), START_PC + 2);
+}
+
+#[test]
+fn sei_delays_irq() {
+    // SEI; NOP
+    let mut cpu = initialize_program(&[0x78, 0xEA]);
+    cpu.clock();
+
+    // An IRQ seen right after SEI is still taken, with I set in the pushed status
+    cpu.interpreter.bus.irq = true;
+    cpu.clock();
+    assert_eq!(cpu.state.pc, TEST_IRQ_HANDLER);
+    assert_eq!(pushed_return_address(&mut cpu), START_PC + 1);
+    assert_eq!(pushed_status(&mut cpu), 0x24);
+}
+
+#[test]
+fn plp_delays_irq() {
+    // PLP; NOP; NOP
+    let mut cpu = initialize_program(&[0x28, 0xEA, 0xEA]);
+    cpu.state.status = Status::INT_DISABLE;
+    cpu.interpreter.bus.write(0x1FE, 0x00);
+    cpu.interpreter.bus.irq = true;
+
+    cpu.clock();
+    assert!(!cpu.state.status.contains(Status::INT_DISABLE));
+
+    cpu.clock();
+    assert_eq!(cpu.state.pc, START_PC + 2);
+
+    cpu.clock();
+    assert_eq!(cpu.state.pc, TEST_IRQ_HANDLER);
+}
+
+#[test]
+fn rti_does_not_delay_irq() {
+    let mut cpu = initialize_program(&[0x40]);
+    cpu.state.status = Status::INT_DISABLE;
+    push_rti_frame(&mut cpu, 0x00, START_PC + 5);
+    cpu.interpreter.bus.irq = true;
+
+    cpu.clock();
+    assert_eq!(cpu.state.pc, START_PC + 5);
+
+    cpu.clock();
+    assert_eq!(cpu.state.pc, TEST_IRQ_HANDLER);
+    assert_eq!(pushed_return_address(&mut cpu), START_PC + 5);
+}
@@ -17,9 +17,31 @@ pub mod constants {
     pub const NES_SCREEN_HEIGHT: u32 = 240;
 }
 
+/// Native frame format of a renderer. In both cases each pixel is RGB888 in the low 24 bits of a
+/// word, U8 is the same data viewed as little-endian bytes
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum PixelFormat {
+    U8,
+    U32,
+}
+
 pub trait Renderer {
     fn draw_line(&mut self, line: &[u8], row: u32);
     fn draw_frame(&mut self, buf: &[u8]);
+
+    /// Display a frame with one pixel per word. Renderers which use this natively should override
+    /// it and report PixelFormat::U32 so frames are never reinterpreted as bytes
+    fn draw_frame_u32(&mut self, buf: &[u32]) {
+        let bytes = buf
+            .iter()
+            .flat_map(|px| px.to_le_bytes())
+            .collect::<Vec<_>>();
+        self.draw_frame(&bytes);
+    }
+
+    fn pixel_format(&self) -> PixelFormat {
+        PixelFormat::U8
+    }
 }
 
 fn dump_texture_buf(buf: &[u8], px_size: usize) {
@@ -45,3 +67,26 @@ fn dump_texture_buf(buf: &[u8], px_size: usize) {
 
     println!("\nTiles:\n{}", &s);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    struct CaptureRenderer(Vec<u8>);
+
+    impl Renderer for CaptureRenderer {
+        fn draw_line(&mut self, _line: &[u8], _row: u32) {}
+        fn draw_frame(&mut self, buf: &[u8]) {
+            self.0 = buf.to_vec();
+        }
+    }
+
+    #[test]
+    fn draw_frame_u32_fallback() {
+        let mut renderer = CaptureRenderer(Vec::new());
+        assert_eq!(renderer.pixel_format(), PixelFormat::U8);
+
+        renderer.draw_frame_u32(&[0x00AABBCC, 0x00112233]);
+        assert_eq!(renderer.0, [0xCC, 0xBB, 0xAA, 0x00, 0x33, 0x22, 0x11, 0x00]);
+    }
+}
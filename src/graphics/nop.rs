@@ -1,4 +1,4 @@
-use super::Renderer;
+use super::{PixelFormat, Renderer};
 
 pub struct NOPRenderer;
 impl NOPRenderer {
@@ -10,4 +10,9 @@ impl NOPRenderer {
 impl Renderer for NOPRenderer {
     fn draw_line(&mut self, _line: &[u8], _row: u32) {}
     fn draw_frame(&mut self, _buf: &[u8]) {}
+    fn draw_frame_u32(&mut self, _buf: &[u32]) {}
+
+    fn pixel_format(&self) -> PixelFormat {
+        PixelFormat::U32
+    }
 }
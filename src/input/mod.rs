@@ -0,0 +1,39 @@
+pub mod sdl2;
+
+use crossbeam::channel;
+
+/// Buttons on the standard NES controller, in the order they are shifted out of $4016/$4017
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Button {
+    A,
+    B,
+    Select,
+    Start,
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Player {
+    One,
+    Two,
+}
+
+/// Requests from the frontend to the CPU thread. These are handled between instructions
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum Command {
+    Stop,
+    TogglePause,
+    Reset,
+    ButtonDown(Player, Button),
+    ButtonUp(Player, Button),
+}
+
+pub type CommandSender = channel::Sender<Command>;
+pub type CommandReceiver = channel::Receiver<Command>;
+
+pub fn command_channel() -> (CommandSender, CommandReceiver) {
+    channel::unbounded()
+}
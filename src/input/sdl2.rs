@@ -0,0 +1,34 @@
+use super::{Button, Command, Player};
+use sdl2::keyboard::{Keycode, Mod};
+
+// FIXME: Make the key bindings configurable, and add a mapping for the second player
+fn button(keycode: Keycode) -> Option<Button> {
+    match keycode {
+        Keycode::Z => Some(Button::A),
+        Keycode::X => Some(Button::B),
+        Keycode::RShift => Some(Button::Select),
+        Keycode::Return => Some(Button::Start),
+        Keycode::Up => Some(Button::Up),
+        Keycode::Down => Some(Button::Down),
+        Keycode::Left => Some(Button::Left),
+        Keycode::Right => Some(Button::Right),
+        _ => None,
+    }
+}
+
+/// Translate a key press into a command for the CPU thread. Hotkeys take priority over the
+/// controller bindings
+pub fn keydown(keycode: Keycode, keymod: Mod) -> Option<Command> {
+    let ctrl = keymod.intersects(Mod::LCTRLMOD | Mod::RCTRLMOD);
+    match keycode {
+        Keycode::Escape => Some(Command::Stop),
+        Keycode::C if ctrl => Some(Command::Stop),
+        Keycode::R if ctrl => Some(Command::Reset),
+        Keycode::P => Some(Command::TogglePause),
+        _ => button(keycode).map(|b| Command::ButtonDown(Player::One, b)),
+    }
+}
+
+pub fn keyup(keycode: Keycode) -> Option<Command> {
+    button(keycode).map(|b| Command::ButtonUp(Player::One, b))
+}
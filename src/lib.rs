@@ -9,9 +9,12 @@ extern crate bitflags;
 pub mod apu;
 pub mod audio;
 pub mod cartridge;
+pub mod compat;
 pub mod cpu;
 pub mod graphics;
+pub mod input;
 pub mod ppu;
+pub mod savestate;
 
 mod bus;
 mod controller;
@@ -21,6 +24,8 @@ mod timer;
 use cartridge::*;
 use cpu::*;
 use crossbeam::thread::scope;
+use input::{Button, Command, CommandReceiver, CommandSender, Player};
+use savestate::{FramePeek, Savestate};
 use std::cell::RefCell;
 use std::sync::{atomic::AtomicBool, Arc};
 use tracing::{event, Level};
@@ -124,6 +129,43 @@ impl<'a> VNES<'a> {
         });
     }
 
+    pub fn interrupt_log(&self) -> &interrupt_log::InterruptLog {
+        self.cpu.interrupt_log()
+    }
+
+    /// Set how many interrupt entries are kept for debugging. A capacity of 0 disables the log
+    pub fn set_interrupt_log_capacity(&mut self, capacity: usize) {
+        self.cpu.interrupt_log_mut().set_capacity(capacity);
+    }
+
+    /// Features the game has used so far which are not emulated, and may cause glitches
+    pub fn unsupported_features(&self) -> compat::Unsupported {
+        self.cpu.bus().unsupported_features()
+    }
+
+    pub fn save_state(&self) -> Savestate {
+        Savestate::new(&self.cpu)
+    }
+
+    /// Restore a savestate. The live renderer and throttling are kept
+    pub fn load_state(&mut self, state: &Savestate) {
+        let throttle = self.cpu.bus().throttle();
+        let renderer = self
+            .cpu
+            .bus_mut()
+            .set_renderer(Box::new(graphics::nop::NOPRenderer::new()));
+
+        self.cpu = state.cpu().clone();
+        self.cpu.bus_mut().set_renderer(renderer);
+        self.cpu.bus_mut().set_throttle(throttle);
+    }
+
+    /// Run one frame from the current state with only `input` held, without affecting the live
+    /// session
+    pub fn peek_frame(&self, input: &[(Player, Button)]) -> FramePeek {
+        self.save_state().run_frame(input)
+    }
+
     pub fn nestest_reset_override(&mut self, pc: u16) {
         self.cpu.nestest_reset_override(pc);
     }
@@ -152,9 +194,28 @@ impl<'a> VNES<'a> {
         ExitStatus::Breakpoint(self.cpu.pc())
     }
 
-    fn sdl_loop(stop_token: Arc<AtomicBool>) {
+    /// Run for at least `cycles` CPU cycles, or until the CPU stops. Instructions are not split, so
+    /// this may overshoot by a few cycles; the number of cycles actually executed is returned so
+    /// the caller can account for it in the next time slice
+    pub fn run_cycles(&mut self, cycles: usize) -> (usize, ExitStatus) {
+        let start = self.cpu.cycles();
+        let mut status = ExitStatus::Continue;
+        while status == ExitStatus::Continue && self.cpu.cycles() - start < cycles {
+            status = self.run_once();
+        }
+
+        (self.cpu.cycles() - start, status)
+    }
+
+    /// Enable or disable limiting the emulation speed to the NTSC clock rate. Embedders that pace
+    /// the emulator themselves, e.g. with run_cycles, should disable this
+    pub fn set_throttle(&mut self, throttle: bool) {
+        self.cpu.bus_mut().set_throttle(throttle);
+    }
+
+    fn sdl_loop(commands: CommandSender, stop_token: Arc<AtomicBool>) {
         use graphics::sdl2::SDL2Intrf;
-        use sdl2::{event::Event, keyboard::Keycode, keyboard::Mod};
+        use sdl2::event::Event;
 
         let mut event_pump = SDL2Intrf::context().event_pump().unwrap();
 
@@ -165,28 +226,76 @@ impl<'a> VNES<'a> {
                 continue;
             }
 
-            match event.unwrap() {
-                Event::Quit { .. }
-                | Event::KeyDown {
-                    keycode: Some(Keycode::Escape),
+            let command = match event.unwrap() {
+                Event::Quit { .. } => Some(Command::Stop),
+                Event::KeyDown {
+                    keycode: Some(keycode),
+                    keymod,
+                    repeat: false,
                     ..
-                }
-                | Event::KeyDown {
-                    keycode: Some(Keycode::C),
-                    keymod: Mod::LCTRLMOD,
+                } => input::sdl2::keydown(keycode, keymod),
+                Event::KeyUp {
+                    keycode: Some(keycode),
                     ..
-                } => {
-                    stop_token.store(true, std::sync::atomic::Ordering::Release);
+                } => input::sdl2::keyup(keycode),
+                ev => {
+                    event!(Level::DEBUG, "Unhandled event {:?}", ev);
+                    None
+                }
+            };
+
+            if let Some(command) = command {
+                // The CPU thread has already exited if the channel is disconnected
+                if commands.send(command).is_err() || command == Command::Stop {
                     return;
                 }
-                ev => event!(Level::DEBUG, "Unhandled event {:?}", ev),
             }
         }
     }
 
-    fn cpu_loop(&mut self, stop_token: Arc<AtomicBool>) -> Result<(), String> {
+    /// Handle all pending commands from the frontend. While paused this blocks until the game is
+    /// either resumed or stopped. Returns false if the CPU should stop
+    fn process_commands(&mut self, commands: &CommandReceiver, paused: &mut bool) -> bool {
+        loop {
+            let command = if *paused {
+                match commands.recv() {
+                    Ok(command) => command,
+                    Err(_) => return false,
+                }
+            } else {
+                match commands.try_recv() {
+                    Ok(command) => command,
+                    Err(_) => return true,
+                }
+            };
+
+            event!(Level::DEBUG, "Command {:?}", command);
+            match command {
+                Command::Stop => return false,
+                Command::TogglePause => *paused = !*paused,
+                Command::Reset => self.reset(),
+                Command::ButtonDown(player, button) => {
+                    self.cpu.bus_mut().set_button(player, button, true)
+                }
+                Command::ButtonUp(player, button) => {
+                    self.cpu.bus_mut().set_button(player, button, false)
+                }
+            }
+        }
+    }
+
+    fn cpu_loop(
+        &mut self,
+        commands: CommandReceiver,
+        stop_token: Arc<AtomicBool>,
+    ) -> Result<(), String> {
+        let mut paused = false;
         let mut inner_loop = || {
             while !stop_token.load(std::sync::atomic::Ordering::Acquire) {
+                if !self.process_commands(&commands, &mut paused) {
+                    return Ok(());
+                }
+
                 match self.run_once() {
                     ExitStatus::Continue => {}
                     ExitStatus::ExitError(e) => return Err(e),
@@ -217,9 +326,10 @@ impl<'a> VNES<'a> {
     }
 
     pub fn play(&mut self) -> Result<(), String> {
+        let (command_sender, command_receiver) = input::command_channel();
         let stop_token_cpu = Arc::new(AtomicBool::new(false));
         if self.headless {
-            return self.cpu_loop(stop_token_cpu);
+            return self.cpu_loop(command_receiver, stop_token_cpu);
         }
 
         scope(|scope| {
@@ -237,14 +347,12 @@ impl<'a> VNES<'a> {
             let cpu_thread = scope
                 .builder()
                 .name("cpu-thread".to_owned())
-                .spawn(|_| self.cpu_loop(stop_token_cpu))
+                .spawn(|_| self.cpu_loop(command_receiver, stop_token_cpu))
                 .unwrap();
 
-            VNES::sdl_loop(stop_token_sdl);
+            VNES::sdl_loop(command_sender, stop_token_sdl);
             cpu_thread.join().unwrap()
         })
         .unwrap()
     }
 }
-
-pub mod input {}
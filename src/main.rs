@@ -2,7 +2,7 @@ use tracing::Level;
 use tracing_subscriber::{fmt, prelude::*, Layer};
 use venus::VNES;
 
-const DEBUG_COMPONENTS: &'static [&str] = &["cpu"];
+const DEBUG_COMPONENTS: &'static [&str] = &["cpu", "compat"];
 
 fn init_tracing() {
     let mut layers = Vec::new();
@@ -41,6 +41,14 @@ fn main() -> Result<(), String> {
     vnes.reset();
     let res = vnes.play();
 
+    let unsupported = vnes.unsupported_features();
+    if !unsupported.is_empty() {
+        println!("This game uses features which are not supported yet:");
+        for description in unsupported.descriptions() {
+            println!("  * {}", description);
+        }
+    }
+
     println!("Exiting VNES");
     res
 }
@@ -1,6 +1,7 @@
 #![allow(non_upper_case_globals)]
 use std::ops::{Deref, DerefMut};
 
+#[derive(Clone)]
 pub struct Memory<const ReadOnly: bool>(Vec<u8>);
 pub type ROM = Memory<true>;
 pub type RAM = Memory<false>;
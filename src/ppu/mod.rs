@@ -3,7 +3,8 @@ mod sprite;
 
 use crate::cartridge::header::{Header, Mirroring};
 use crate::cartridge::Cartridge;
-use crate::graphics::Renderer;
+use crate::compat::{CompatTracker, Unsupported};
+use crate::graphics::{nop::NOPRenderer, PixelFormat, Renderer};
 use crate::memory::{RAM, ROM};
 use crate::timer;
 use crate::{NES_FRAME_HEIGHT_PX, NES_FRAME_WIDTH_PX};
@@ -44,13 +45,13 @@ const PALETTE_COLOR_LUT: [u32; 64] = [
     0x000000,
 ];
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Flags {
     pub odd: bool,
     pub has_nmi: bool,
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 struct Tile {
     number: usize,
     nametable_byte: u8,
@@ -61,6 +62,7 @@ struct Tile {
 
 const MAX_SPRITES: usize = 8;
 
+#[derive(Clone)]
 struct OamSecondary {
     sprites: [Sprite; MAX_SPRITES],
     has_sprite_0: bool,
@@ -117,9 +119,11 @@ enum PpuState {
 
 // A simple tripple-buffered frame buffer so the PPU can draw safely while offloading rendering to
 // another thread
+#[derive(Clone)]
 struct FrameBuffer {
     buffers: Box<[[u32; FRAME_SIZE]; 2]>,
     index: usize,
+    completed: usize,
 }
 
 impl std::ops::Index<usize> for FrameBuffer {
@@ -140,13 +144,27 @@ impl FrameBuffer {
         Self {
             buffers: Box::new([[0_u32; FRAME_SIZE_BYTES / PX_SIZE_BYTES]; 2]),
             index: 0,
+            completed: 0,
         }
     }
 
+    /// Mark the buffer currently being drawn as the last complete frame
+    fn complete(&mut self) {
+        self.completed = self.index;
+    }
+
+    fn completed(&self) -> &[u32; FRAME_SIZE] {
+        &self.buffers[self.completed]
+    }
+
     fn swap(&mut self) {
         self.index = (self.index + 1) % self.buffers.len();
     }
 
+    fn current(&self) -> &[u32; FRAME_SIZE] {
+        &self.buffers[self.index]
+    }
+
     fn to_bytes(&self) -> &[u8; FRAME_SIZE_BYTES] {
         unsafe { std::mem::transmute(&self.buffers[self.index]) }
     }
@@ -184,6 +202,36 @@ pub struct PPU {
     palette_table: [u8; 32],
 
     needs_render: bool,
+    compat: CompatTracker,
+}
+
+// The renderer is not part of the emulated state, so a cloned PPU draws nothing until it is given
+// a renderer with set_renderer
+impl Clone for PPU {
+    fn clone(&self) -> Self {
+        PPU {
+            frame_buf: self.frame_buf.clone(),
+            cartridge_header: self.cartridge_header.clone(),
+            cartridge_chr: self.cartridge_chr.clone(),
+            registers: self.registers.clone(),
+            ppudata_buffer: self.ppudata_buffer,
+            flags: self.flags.clone(),
+            vram: self.vram.clone(),
+            renderer: Box::new(NOPRenderer::new()),
+            oam_primary: self.oam_primary,
+            oam_secondary: self.oam_secondary.clone(),
+            cycles_behind: self.cycles_behind,
+            ppu_cycle: self.ppu_cycle,
+            scanline: self.scanline,
+            frame: self.frame,
+            current_state: self.current_state,
+            transition_lut: self.transition_lut,
+            tile_q: self.tile_q.clone(),
+            palette_table: self.palette_table,
+            needs_render: self.needs_render,
+            compat: self.compat.clone(),
+        }
+    }
 }
 
 const WHITE: [u8; 4] = [0xff; 4];
@@ -258,9 +306,31 @@ impl PPU {
             vram: RAM::with_size(PPU_VRAM_SIZE),
 
             needs_render: true,
+            compat: CompatTracker::default(),
         }
     }
 
+    /// Replace the renderer, returning the previous one. The new renderer is given the current frame
+    /// even if no pixels change
+    pub fn set_renderer(&mut self, renderer: Box<dyn Renderer>) -> Box<dyn Renderer> {
+        self.needs_render = true;
+        std::mem::replace(&mut self.renderer, renderer)
+    }
+
+    /// Number of frames completed since power on
+    pub fn frame(&self) -> usize {
+        self.frame
+    }
+
+    /// The last complete frame, one RGB888 pixel per word
+    pub fn frame_buffer(&self) -> &[u32] {
+        self.frame_buf.completed()
+    }
+
+    pub fn unsupported_features(&self) -> Unsupported {
+        self.compat.seen()
+    }
+
     pub fn cycle(&self) -> i32 {
         (self.total_ppu_cycles() % CYCLES_PER_SCANLINE) as i32
     }
@@ -355,7 +425,15 @@ impl PPU {
                 self.registers.ctrl = val;
                 self.registers.addr.set_nametable(val);
             }
-            1 => self.registers.mask = val,
+            1 => {
+                if val & (PpuMask::EMPH_RED | PpuMask::EMPH_GREEN | PpuMask::EMPH_BLUE) != 0 {
+                    self.compat.report(Unsupported::COLOR_EMPHASIS);
+                }
+                if val & PpuMask::GRAYSCALE != 0 {
+                    self.compat.report(Unsupported::GRAYSCALE);
+                }
+                self.registers.mask = val;
+            }
             2 => self.registers.status = val,
             3 => self.registers.oamaddr = val,
             4 => {
@@ -399,7 +477,10 @@ impl PPU {
         match addr {
             // Pattern tables 0 and 1
             0..=0x1FFF => {
-                // Ignore writes to CHR
+                // Ignore writes to CHR. On CHR ROM carts the hardware ignores them too
+                if self.cartridge_header.get_chr_ram_size() == 0 {
+                    self.compat.report(Unsupported::CHR_WRITE);
+                }
                 event!(
                     Level::DEBUG,
                     "ignoring write to CHR ROM at {:#x} of {:#x}",
@@ -511,6 +592,7 @@ impl PPU {
 
     fn do_end_frame(&mut self) {
         self.frame += 1;
+        self.frame_buf.complete();
         self.flags.has_nmi = false;
         self.flags.odd = !self.flags.odd;
 
@@ -1195,8 +1277,10 @@ impl PPU {
 
         self.needs_render = false;
         timer::timed!("ppu::render frame", {
-            self.renderer
-                .draw_frame(self.frame_buf.to_bytes().as_slice());
+            match self.renderer.pixel_format() {
+                PixelFormat::U8 => self.renderer.draw_frame(self.frame_buf.to_bytes()),
+                PixelFormat::U32 => self.renderer.draw_frame_u32(self.frame_buf.current()),
+            }
             self.frame_buf.swap();
         });
     }
@@ -1222,6 +1306,40 @@ mod test {
         assert_eq!(mirror(&Mirroring::Horizontal, 0x0C38), 0x0838);
     }
 
+    #[test]
+    fn report_unsupported() {
+        // The default cartridge has no CHR ROM, so it uses CHR RAM
+        let mut ppu = PPU::new(&Cartridge::default(), Box::new(NOPRenderer::new()));
+        assert!(ppu.unsupported_features().is_empty());
+
+        // PPUMASK with rendering enabled and no emphasis is fine
+        ppu.register_write(1, PpuMask::SHOW_BG | PpuMask::SHOW_SPRITES);
+        assert!(ppu.unsupported_features().is_empty());
+
+        ppu.register_write(1, PpuMask::EMPH_RED | PpuMask::GRAYSCALE);
+        assert_eq!(
+            ppu.unsupported_features(),
+            Unsupported::COLOR_EMPHASIS | Unsupported::GRAYSCALE
+        );
+
+        // PPUADDR = $0010, then PPUDATA writes to the pattern table
+        ppu.register_write(6, 0x00);
+        ppu.register_write(6, 0x10);
+        ppu.register_write(7, 0xFF);
+        assert!(ppu.unsupported_features().contains(Unsupported::CHR_WRITE));
+
+        // Writes to CHR ROM are ignored by the hardware as well
+        let mut header = [0; 16];
+        header[4] = 2;
+        header[5] = 1;
+        let mut ppu = PPU::new(&Cartridge::default(), Box::new(NOPRenderer::new()));
+        ppu.cartridge_header = Header::from(&header);
+        ppu.register_write(6, 0x00);
+        ppu.register_write(6, 0x10);
+        ppu.register_write(7, 0xFF);
+        assert!(ppu.unsupported_features().is_empty());
+    }
+
     #[test]
     fn lohi_to_index() {
         assert_eq!(
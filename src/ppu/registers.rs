@@ -31,7 +31,7 @@ impl PpuStatus {
     pub const PREV_LSB: u8 = 0x1F;
 }
 
-#[derive(Default)]
+#[derive(Clone, Default)]
 pub struct Registers {
     pub ctrl: u8,
     pub mask: u8,
@@ -0,0 +1,137 @@
+use crate::input::{Button, Player};
+use crate::{ExitStatus, NesCPU};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+
+/// A copy of the whole emulated console. The renderer is not part of the state, so running a
+/// savestate directly is always headless
+#[derive(Clone)]
+pub struct Savestate {
+    cpu: NesCPU,
+}
+
+/// State of the console after running a savestate for one frame
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct FramePeek {
+    pub status: ExitStatus,
+    pub frame_hash: u64,
+    pub ram: Vec<u8>,
+}
+
+impl Savestate {
+    pub(crate) fn new(cpu: &NesCPU) -> Self {
+        Savestate { cpu: cpu.clone() }
+    }
+
+    pub(crate) fn cpu(&self) -> &NesCPU {
+        &self.cpu
+    }
+
+    /// Number of frames completed since power on
+    pub fn frame(&self) -> usize {
+        self.cpu.bus().frame()
+    }
+
+    pub fn pc(&self) -> u16 {
+        self.cpu.pc()
+    }
+
+    /// Run as fast as possible until the PPU completes the current frame, with only the buttons in
+    /// `input` held. Stops early if the CPU does not return ExitStatus::Continue
+    pub fn run_frame(&mut self, input: &[(Player, Button)]) -> FramePeek {
+        let bus = self.cpu.bus_mut();
+        bus.set_throttle(false);
+        bus.release_all_buttons();
+        for &(player, button) in input {
+            bus.set_button(player, button, true);
+        }
+
+        let frame = self.frame();
+        let mut status = ExitStatus::Continue;
+        while status == ExitStatus::Continue && self.frame() == frame {
+            status = self.cpu.clock();
+        }
+
+        let mut hasher = DefaultHasher::new();
+        self.cpu.bus().frame_buffer().hash(&mut hasher);
+
+        FramePeek {
+            status,
+            frame_hash: hasher.finish(),
+            ram: self.cpu.bus().cpu_ram().to_vec(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::graphics::Renderer;
+    use crate::VNES;
+    use std::cell::Cell;
+    use std::rc::Rc;
+
+    #[derive(Clone, Default)]
+    struct FrameCounter(Rc<Cell<usize>>);
+
+    impl Renderer for FrameCounter {
+        fn draw_line(&mut self, _line: &[u8], _row: u32) {}
+        fn draw_frame(&mut self, _buf: &[u8]) {
+            self.0.set(self.0.get() + 1);
+        }
+    }
+
+    #[test]
+    fn peek_frame() {
+        let mut nes = VNES::new_headless("test/nestest.nes").expect("Could not load nestest ROM");
+        nes.reset();
+
+        let live = nes.save_state();
+        let first = nes.peek_frame(&[]);
+        let second = nes.peek_frame(&[]);
+        assert_eq!(first.status, ExitStatus::Continue);
+        assert_eq!(first, second, "Peeking should be deterministic");
+
+        // The live session is not advanced
+        let after = nes.save_state();
+        assert_eq!(after.frame(), live.frame());
+        assert_eq!(after.pc(), live.pc());
+    }
+
+    #[test]
+    fn load_state() {
+        let mut nes = VNES::new_headless("test/nestest.nes").expect("Could not load nestest ROM");
+        nes.reset();
+
+        let mut state = nes.save_state();
+        let peek = state.run_frame(&[(Player::One, Button::Start)]);
+        assert_eq!(state.frame(), nes.save_state().frame() + 1);
+
+        nes.load_state(&state);
+        assert_eq!(nes.save_state().frame(), state.frame());
+        assert_eq!(nes.save_state().run_frame(&[]).status, peek.status);
+    }
+
+    #[test]
+    fn load_state_redraws() {
+        let mut nes = VNES::new_headless("test/nestest.nes").expect("Could not load nestest ROM");
+        nes.reset();
+        nes.set_throttle(false);
+
+        // Let nestest settle on its menu, so later frames do not change any pixels
+        let mut state = nes.save_state();
+        for _ in 0..10 {
+            state.run_frame(&[]);
+        }
+
+        let drawn = FrameCounter::default();
+        nes.cpu.bus_mut().set_renderer(Box::new(drawn.clone()));
+        nes.load_state(&state);
+
+        let frame = state.frame();
+        while nes.cpu.bus().frame() == frame {
+            nes.run_once();
+        }
+        assert_eq!(drawn.0.get(), 1, "The restored frame should be drawn");
+    }
+}
@@ -143,6 +143,25 @@ fn nestest() {
     }
 }
 
+#[test]
+fn run_cycles() {
+    let mut nes = VNES::new_headless("test/nestest.nes").expect("Could not load nestest ROM");
+
+    const NESTEST_AUTOMATED_START: u16 = 0xC000;
+    nes.nestest_reset_override(NESTEST_AUTOMATED_START);
+    nes.set_throttle(false);
+
+    assert_eq!(nes.run_cycles(0), (0, ExitStatus::Continue));
+
+    // No instruction takes more than 7 cycles
+    const MAX_INSTRUCTION_CYCLES: usize = 7;
+    for _ in 0..100 {
+        let (executed, status) = nes.run_cycles(100);
+        assert_eq!(status, ExitStatus::Continue);
+        assert!((100..100 + MAX_INSTRUCTION_CYCLES).contains(&executed));
+    }
+}
+
 fn run_test_rom(s: &str) {
     use std::sync::Once;
     static INIT: Once = Once::new();
@@ -33,6 +33,7 @@ macro_rules! timed {
 }
 pub(crate) use timed;
 
+#[derive(Clone, Copy)]
 pub struct FastInstant(u64);
 
 type TimeResultRef = Arc<TimeResult>;